@@ -39,6 +39,82 @@ impl Generator for EpoxyGenerator {
     }
 }
 
+/// Like `EpoxyGenerator`, but every generated function traces its call (name and argument
+/// values) and checks `glGetError` immediately afterwards, modeled on gl_generator's
+/// `debug_struct_gen`. `GetError` itself gets no post-call check, and no check runs until
+/// `GetError` has been loaded (see `write_fns_debug`). Intended for debug builds only -- pick it at build time via the
+/// `debug_gl_trace` feature so release builds keep `EpoxyGenerator`'s zero-overhead path.
+#[allow(missing_copy_implementations)]
+pub struct DebugEpoxyGenerator;
+
+impl Generator for DebugEpoxyGenerator {
+    fn write<W>(&self, registry: &Registry, dest: &mut W) -> io::Result<()>
+        where W: io::Write
+    {
+        write_header(dest)?;
+        write_metaloadfn(dest)?;
+        write_type_aliases(registry, dest)?;
+        write_enums(registry, dest)?;
+        write_error_string_fn(dest)?;
+        write_fns_debug(registry, dest)?;
+        write_fnptr_struct_def(dest)?;
+        write_ptrs(registry, dest)?;
+        write_fn_mods(registry, dest)?;
+        write_error_fns(dest)?;
+        write_load_fn(registry, dest)?;
+        write_get_proc_addr(registry, dest)?;
+        Ok(())
+    }
+}
+
+/// Emits a `struct Epoxy` holding one `FnPtr` field per GL command, loaded by
+/// `Epoxy::load_with`, instead of `EpoxyGenerator`'s `mod storage { static mut ... }`.
+///
+/// `static mut` storage is unsound under Rust's aliasing rules and forces a single global
+/// dispatch table per process; a caller-owned `Epoxy` value can be loaded once per GL context,
+/// moved between threads, and dropped when that context goes away. Modeled on gl_generator's
+/// struct_gen/static_struct_gen.
+#[allow(missing_copy_implementations)]
+pub struct StructEpoxyGenerator;
+
+impl Generator for StructEpoxyGenerator {
+    fn write<W>(&self, registry: &Registry, dest: &mut W) -> io::Result<()>
+        where W: io::Write
+    {
+        write_header(dest)?;
+        write_metaloadfn(dest)?;
+        write_type_aliases(registry, dest)?;
+        write_enums(registry, dest)?;
+        write_fnptr_struct_def(dest)?;
+        write_error_fns(dest)?;
+        write_struct_def(registry, dest)?;
+        write_struct_load_with(registry, dest)?;
+        write_struct_methods(registry, dest)?;
+        Ok(())
+    }
+}
+
+/// libepoxy's `epoxy_glFoo` symbols are themselves self-resolving dispatch pointers, so unlike
+/// `EpoxyGenerator` this links straight against them and skips `load_with`/`metaloadfn`
+/// entirely -- no `storage` module, `FnPtr`, or `PMISSING_FN_EXIT`, at the cost of giving up a
+/// pluggable `loadfn`.
+#[allow(missing_copy_implementations)]
+pub struct StaticEpoxyGenerator;
+
+impl Generator for StaticEpoxyGenerator {
+    fn write<W>(&self, registry: &Registry, dest: &mut W) -> io::Result<()>
+        where W: io::Write
+    {
+        write_header(dest)?;
+        write_type_aliases(registry, dest)?;
+        write_enums(registry, dest)?;
+        write_static_fns(registry, dest)?;
+        write_static_extern_block(registry, dest)?;
+        write_get_proc_addr(registry, dest)?;
+        Ok(())
+    }
+}
+
 /// Creates a `__gl_imports` module which contains all the external symbols that we need for the
 ///  bindings.
 fn write_header<W>(dest: &mut W) -> io::Result<()>
@@ -46,6 +122,7 @@ fn write_header<W>(dest: &mut W) -> io::Result<()>
 {
     writeln!(dest,
              r#"
+        #[allow(unused_imports)]
         mod __gl_imports {{
             pub extern crate libc;
             pub use std::mem;
@@ -133,6 +210,80 @@ fn write_fns<W>(registry: &Registry, dest: &mut W) -> io::Result<()> where W: io
     Ok(())
 }
 
+/// Creates the functions corresponding to the GL commands, instrumented with call tracing and
+/// an automatic post-call `glGetError` check, modeled on gl_generator's `debug_struct_gen`.
+///
+/// `GetError` itself is never wrapped with the post-call check (it would recurse forever), and
+/// the check is skipped entirely until `GetError` has been loaded.
+fn write_fns_debug<W>(registry: &Registry, dest: &mut W) -> io::Result<()> where W: io::Write {
+    for c in &registry.cmds {
+        if let Some(v) = registry.aliases.get(&c.proto.ident) {
+            writeln!(dest, "/// Fallbacks: {}", v.join(", "))?;
+        }
+
+        let idents = gen_parameters(c, true, false).join(", ");
+        let trace_args = gen_parameters(c, true, false).iter()
+            .map(|p| format!("{} = {{:?}}", p))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let check_error = if c.proto.ident == "GetError" {
+            String::new()
+        } else {
+            format!(r#"
+                if storage::GetError.is_loaded {{
+                    let __epoxy_error = __gl_imports::mem::transmute::<_, extern "system" fn() -> types::GLenum>
+                        (*storage::GetError.pf)();
+                    if __epoxy_error != 0 {{
+                        eprintln!("{name}: {{}}", error_string(__epoxy_error));
+                    }}
+                }}"#,
+                name = c.proto.ident,
+            )
+        };
+
+        writeln!(dest, r#"
+            #[allow(non_snake_case, unused_variables, dead_code)] #[inline]
+            pub unsafe fn {name}({params}) -> {return_suffix} {{
+                println!("{name}({trace_args})", {idents});
+                let __epoxy_result = __gl_imports::mem::transmute::<_, extern "system" fn({typed_params}) -> {return_suffix}>
+                    (*storage::{name}.pf)({idents});{check_error}
+                __epoxy_result
+            }}"#,
+            name = c.proto.ident,
+            params = gen_parameters(c, true, true).join(", "),
+            typed_params = gen_parameters(c, false, true).join(", "),
+            return_suffix = gen_return_type(c),
+            idents = idents,
+            trace_args = trace_args,
+            check_error = check_error,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Creates `error_string`, which decodes a `GLenum` error code returned by `glGetError` into
+/// its constant name for the `DebugEpoxyGenerator` trace output.
+fn write_error_string_fn<W>(dest: &mut W) -> io::Result<()> where W: io::Write {
+    writeln!(dest, r#"
+        #[allow(dead_code)]
+        fn error_string(error: types::GLenum) -> &'static str {{
+            match error {{
+                0x0500 => "GL_INVALID_ENUM",
+                0x0501 => "GL_INVALID_VALUE",
+                0x0502 => "GL_INVALID_OPERATION",
+                0x0503 => "GL_STACK_OVERFLOW",
+                0x0504 => "GL_STACK_UNDERFLOW",
+                0x0505 => "GL_OUT_OF_MEMORY",
+                0x0506 => "GL_INVALID_FRAMEBUFFER_OPERATION",
+                0x0507 => "GL_CONTEXT_LOST",
+                _ => "GL_UNKNOWN_ERROR",
+            }}
+        }}"#,
+    )
+}
+
 fn gen_return_type(cmd: &Cmd) -> String {
     // turn the return type into a Rust type
     let ty = &cmd.proto.ty;
@@ -171,6 +322,96 @@ fn write_fnptr_struct_def<W>(dest: &mut W) -> io::Result<()> where W: io::Write
     ")
 }
 
+/// Creates the `Epoxy` struct, with one `FnPtr` field per GL command in the registry.
+///
+/// `FnPtr` wraps a raw `*const *const c_void`, which makes `Epoxy` `!Send` by default even
+/// though the pointers it holds are read-only dispatch-table entries resolved once by
+/// `load_with` and never mutated afterwards -- safe to hand to another thread. `unsafe impl
+/// Send` records that guarantee explicitly instead of leaving callers to work around `!Send`.
+fn write_struct_def<W>(registry: &Registry, dest: &mut W) -> io::Result<()> where W: io::Write {
+    writeln!(dest, r#"
+        #[allow(non_snake_case)]
+        pub struct Epoxy {{"#)?;
+
+    for c in &registry.cmds {
+        writeln!(dest, "    pub {name}: FnPtr,", name = c.proto.ident)?;
+    }
+
+    writeln!(dest, "}}")?;
+
+    writeln!(dest, r#"
+        // Safety: `FnPtr`'s raw pointer is a read-only dispatch-table entry filled in once by
+        // `load_with` and never mutated afterwards, so it's safe to move an `Epoxy` to another
+        // thread.
+        unsafe impl Send for Epoxy {{}}"#)
+}
+
+/// Creates `Epoxy::load_with`, which fills in every field via `metaloadfn`.
+fn write_struct_load_with<W>(registry: &Registry, dest: &mut W) -> io::Result<()> where W: io::Write {
+    writeln!(dest, r#"
+        impl Epoxy {{
+            #[allow(dead_code)]
+            pub fn load_with<F>(mut loadfn: F) -> Epoxy
+                where F: FnMut(&str) -> *const __gl_imports::raw::c_void
+            {{
+                Epoxy {{"#)?;
+
+    for c in &registry.cmds {
+        let fallbacks = match registry.aliases.get(&c.proto.ident) {
+            Some(v) => {
+                let names = v.iter().map(|name| format!(r#""epoxy_{}""#,
+                    gen_symbol_name(registry.api, &name[..]))).collect::<Vec<_>>();
+                format!("&[{}]", names.join(", "))
+            },
+            None => "&[]".to_string(),
+        };
+        let symbol = gen_symbol_name(registry.api, &c.proto.ident[..]);
+
+        writeln!(dest,
+            r#"                    {name}: FnPtr::new(metaloadfn(|s| loadfn(s), "epoxy_{symbol}", {fallbacks})),"#,
+            name = c.proto.ident,
+            symbol = symbol,
+            fallbacks = fallbacks,
+        )?;
+    }
+
+    writeln!(dest, r#"
+                }}
+            }}
+        }}"#)
+}
+
+/// Creates the `Epoxy` inherent methods: one `{name}` call wrapper plus an `{name}_is_loaded`
+/// accessor per GL command in the registry.
+fn write_struct_methods<W>(registry: &Registry, dest: &mut W) -> io::Result<()> where W: io::Write {
+    writeln!(dest, "impl Epoxy {{")?;
+
+    for c in &registry.cmds {
+        if let Some(v) = registry.aliases.get(&c.proto.ident) {
+            writeln!(dest, "/// Fallbacks: {}", v.join(", "))?;
+        }
+
+        writeln!(dest, r#"
+            #[allow(non_snake_case, unused_variables, dead_code)] #[inline]
+            pub unsafe fn {name}(&self, {params}) -> {return_suffix} {{
+                __gl_imports::mem::transmute::<_, extern "system" fn({typed_params}) -> {return_suffix}>
+                    (*self.{name}.pf)({idents})
+            }}
+            #[allow(non_snake_case, dead_code)] #[inline]
+            pub fn {name}_is_loaded(&self) -> bool {{
+                self.{name}.is_loaded
+            }}"#,
+            name = c.proto.ident,
+            params = gen_parameters(c, true, true).join(", "),
+            typed_params = gen_parameters(c, false, true).join(", "),
+            return_suffix = gen_return_type(c),
+            idents = gen_parameters(c, true, false).join(", "),
+        )?;
+    }
+
+    writeln!(dest, "}}")
+}
+
 /// Creates a `storage` module which contains a static `FnPtr` per GL command in the registry.
 fn write_ptrs<W>(registry: &Registry, dest: &mut W) -> io::Result<()> where W: io::Write {
     writeln!(dest,
@@ -200,7 +441,7 @@ fn write_fn_mods<W>(registry: &Registry, dest: &mut W) -> io::Result<()> where W
     for c in &registry.cmds {
         let fallbacks = match registry.aliases.get(&c.proto.ident) {
             Some(v) => {
-                let names = v.iter().map(|name| format!(r#""{}""#,
+                let names = v.iter().map(|name| format!(r#""epoxy_{}""#,
                     gen_symbol_name(registry.api, &name[..]))).collect::<Vec<_>>();
                 format!("&[{}]", names.join(", "))
             },
@@ -242,7 +483,7 @@ fn write_fn_mods<W>(registry: &Registry, dest: &mut W) -> io::Result<()> where W
 fn write_error_fns<W>(dest: &mut W) -> io::Result<()> where W: io::Write {
     writeln!(dest, r#"
         #[inline(never)]
-        extern fn missing_fn_exit() {{
+        extern "C" fn missing_fn_exit() {{
             println!("function was not loaded");
             __gl_imports::exit(1);
         }}
@@ -269,6 +510,54 @@ fn write_load_fn<W>(registry: &Registry, dest: &mut W) -> io::Result<()> where W
     ")
 }
 
+/// Creates the functions corresponding to the GL commands, calling straight through to the
+/// `epoxy_{symbol}` dispatch symbols exposed by `write_static_extern_block` instead of going
+/// through `storage`/`load_with`.
+fn write_static_fns<W>(registry: &Registry, dest: &mut W) -> io::Result<()> where W: io::Write {
+    for c in &registry.cmds {
+        if let Some(v) = registry.aliases.get(&c.proto.ident) {
+            writeln!(dest, "/// Fallbacks: {}", v.join(", "))?;
+        }
+
+        writeln!(dest, r#"
+            #[allow(non_snake_case, unused_variables, dead_code)] #[inline]
+            pub unsafe fn {name}({params}) -> {return_suffix} {{
+                __gl_imports::mem::transmute::<_, extern "system" fn({typed_params}) -> {return_suffix}>
+                    (ffi::epoxy_{symbol})({idents})
+            }}"#,
+            name = c.proto.ident,
+            params = gen_parameters(c, true, true).join(", "),
+            typed_params = gen_parameters(c, false, true).join(", "),
+            return_suffix = gen_return_type(c),
+            idents = gen_parameters(c, true, false).join(", "),
+            symbol = gen_symbol_name(registry.api, &c.proto.ident[..]),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Creates an `ffi` module exposing each libepoxy `epoxy_{symbol}` dispatch symbol directly, so
+/// `write_static_fns` can call through to it without a `storage`/`FnPtr`/`load_with` step.
+fn write_static_extern_block<W>(registry: &Registry, dest: &mut W) -> io::Result<()> where W: io::Write {
+    writeln!(dest, r#"
+        #[allow(non_snake_case)]
+        #[allow(unused_variables)]
+        #[allow(dead_code)]
+        mod ffi {{
+            use super::__gl_imports;
+            extern "system" {{"#)?;
+
+    for c in &registry.cmds {
+        writeln!(dest,
+            r#"#[link_name="epoxy_{symbol}"] pub static epoxy_{symbol}: *const *const __gl_imports::raw::c_void;"#,
+            symbol = gen_symbol_name(registry.api, &c.proto.ident[..]),
+        )?;
+    }
+
+    writeln!(dest, "}}}}")
+}
+
 /// Creates the `get_proc_addr` function.
 ///
 /// The function adds in a layer of indirection, but allows compatibility with the `gl` crate
@@ -0,0 +1,9 @@
+//! Raw FFI bindings to libepoxy, generated at build time by `build.rs`.
+//!
+//! These lints don't carry their usual weight over generated raw-FFI wrappers: every command
+//! is `unsafe` for the same documented reason (it calls into a C entry point libepoxy resolved),
+//! and every dispatch call transmutes a type-erased function pointer to its real signature by
+//! construction.
+#![allow(clippy::missing_safety_doc, clippy::missing_transmute_annotations, clippy::redundant_slicing)]
+
+include!(concat!(env!("OUT_DIR"), "/bindings.rs"));